@@ -5,29 +5,101 @@ use near_sdk::{
     env,
     near_bindgen,
     serde::{Deserialize, Serialize},
-    json_types::{ValidAccountId, U128},
+    json_types::U128,
     Balance, PanicOnDefault, Promise,
 };
 
+// Tracks where a task sits in the enqueue -> assign -> submit -> review pipeline.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum TaskStatus {
+    Enqueued,
+    Assigned,
+    Submitted,
+    InReview,
+    Accepted,
+    Rejected,
+    Expired,
+    Failed,
+}
+
+// A single entry in a task's append-only audit trail.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TaskEvent {
+    status: TaskStatus,
+    actor_account: String,
+    timestamp: u64,
+}
+
 // Define the data structure for a task
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
 pub struct Task {
     image_url: String,
     descriptions: String,
+    status: TaskStatus,
+    priority: u8,
+    enqueued_at: u64,
+    retry_count: u32,
     assigned_to: Option<String>,
     assigned_at: Option<u64>,
+    deadline: Option<u64>,
     reviewed_by: Option<String>,
     reviewed_at: Option<u64>,
     is_completed: bool,
 }
 
+// Weights for the urgency score used to order the task queue. Tuned so that
+// priority dominates, age provides gradual aging pressure, and retries nudge
+// a task back up after being bounced through reclaim/rejection.
+const URGENCY_WEIGHT_PRIORITY: u64 = 100;
+const URGENCY_WEIGHT_AGE_HOURS: u64 = 5;
+const URGENCY_WEIGHT_RETRY: u64 = 20;
+const NANOS_PER_HOUR: u64 = 60 * 60 * 1_000_000_000;
+
 // Define the data structure for a review task
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
 pub struct ReviewTask {
     task_id: u64,
-    reviewed_by: String,
-    reviewed_at: u64,
+    reviewed_by: Option<String>,
+    reviewed_at: Option<u64>,
     is_accepted: bool,
+    retry_count: u32,
+    overturned: bool,
+    slashed: bool,
+}
+
+// Optional constraints used to narrow down a `get_tasks`/`count_tasks` query.
+// `None` on a field means "don't filter on this".
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TaskFilter {
+    assigned_to: Option<String>,
+    status: Option<TaskStatus>,
+    only_completed: Option<bool>,
+}
+
+impl TaskFilter {
+    fn matches(&self, task: &Task) -> bool {
+        if let Some(assigned_to) = &self.assigned_to {
+            if task.assigned_to.as_ref() != Some(assigned_to) {
+                return false;
+            }
+        }
+        if let Some(status) = self.status {
+            if task.status != status {
+                return false;
+            }
+        }
+        if let Some(only_completed) = self.only_completed {
+            if task.is_completed != only_completed {
+                return false;
+            }
+        }
+        true
+    }
 }
 
 // Define the main contract structure
@@ -38,116 +110,295 @@ pub struct TaskManager {
     review_tasks: UnorderedMap<u64, ReviewTask>,
     task_queue: Vector<u64>,
     review_queue: Vector<u64>,
+    task_events: UnorderedMap<u64, Vec<TaskEvent>>,
     task_counter: u64,
     review_counter: u64,
     payout_account: String,
+    assignment_ttl_ns: u64,
+    reviewer_stakes: UnorderedMap<String, Balance>,
+    reviewer_penalties: UnorderedMap<String, Balance>,
+    slash_fraction_bps: u16,
+    max_retries: u32,
+    dead_letter_queue: Vector<u64>,
+    dedup_index: UnorderedMap<String, u64>,
 }
 
 // Implement the public methods for the smart contract
 #[near_bindgen]
 impl TaskManager {
-      
-    // Method to add a new task to the queue
-    pub fn add_task(&mut self, image_url: String) {
-      let task_id = self.task_counter;
+
+    #[init]
+    pub fn new(
+        payout_account: String,
+        assignment_ttl_ns: u64,
+        slash_fraction_bps: u16,
+        max_retries: u32,
+    ) -> Self {
+        assert!(slash_fraction_bps <= 10_000, "slash_fraction_bps must be a basis-point fraction (<= 10000)");
+        Self {
+            tasks: UnorderedMap::new(b"t".to_vec()),
+            review_tasks: UnorderedMap::new(b"r".to_vec()),
+            task_queue: Vector::new(b"q".to_vec()),
+            review_queue: Vector::new(b"v".to_vec()),
+            task_events: UnorderedMap::new(b"e".to_vec()),
+            task_counter: 0,
+            review_counter: 0,
+            assignment_ttl_ns,
+            reviewer_stakes: UnorderedMap::new(b"s".to_vec()),
+            reviewer_penalties: UnorderedMap::new(b"p".to_vec()),
+            slash_fraction_bps,
+            max_retries,
+            dead_letter_queue: Vector::new(b"d".to_vec()),
+            dedup_index: UnorderedMap::new(b"u".to_vec()),
+            payout_account,
+        }
+    }
+
+    // Appends an event to a task's history and updates its current status.
+    fn record_event(&mut self, task_id: u64, status: TaskStatus, actor_account: String) {
+        let mut task = self.tasks.get(&task_id).expect("Invalid task ID");
+        task.status = status;
+        self.tasks.insert(&task_id, &task);
+
+        let mut history = self.task_events.get(&task_id).unwrap_or_default();
+        history.push(TaskEvent {
+            status,
+            actor_account,
+            timestamp: env::block_timestamp(),
+        });
+        self.task_events.insert(&task_id, &history);
+    }
+
+    // Enqueues a task, or returns the existing one if the same content was
+    // already submitted and is still active. The dedup key is `uniq_hash` when
+    // given, otherwise the `image_url` itself. Returns `(task_id, is_new)` so
+    // callers can tell an enqueue from a dedup hit.
+    pub fn add_task(&mut self, image_url: String, priority: u8, uniq_hash: Option<String>) -> (u64, bool) {
+        let dedup_key = uniq_hash.unwrap_or_else(|| image_url.clone());
+        if let Some(existing_id) = self.dedup_index.get(&dedup_key) {
+            let existing_task = self.tasks.get(&existing_id).expect("Invalid task ID");
+            if existing_task.status != TaskStatus::Accepted && existing_task.status != TaskStatus::Failed {
+                return (existing_id, false);
+            }
+        }
+
+        let task_id = self.task_counter;
         let task = Task {
             image_url,
-            descriptions,
+            descriptions: String::new(),
+            status: TaskStatus::Enqueued,
+            priority,
+            enqueued_at: env::block_timestamp(),
+            retry_count: 0,
             assigned_to: None,
             assigned_at: None,
+            deadline: None,
             reviewed_by: None,
             reviewed_at: None,
             is_completed: false,
         };
         self.tasks.insert(&task_id, &task);
         self.task_queue.push(&task_id);
+        self.dedup_index.insert(&dedup_key, &task_id);
         self.task_counter += 1;
-        task_id
+        self.record_event(task_id, TaskStatus::Enqueued, env::predecessor_account_id());
+        (task_id, true)
     }
 
     // Method to assign a task to a user
     pub fn assign_task(&mut self, task_id: u64, user_account: String) {
-        assert!(self.task_queue.contains(&task_id), "Task not in queue");
-        let mut task = self.tasks.get(&task_id).unwrap();
-        assert!(task.assigned_to.is_none(), "Task already assigned");
+        assert!(self.task_queue.iter().any(|x| x == task_id), "Task not in queue");
+        let mut task = self.tasks.get(&task_id).expect("Invalid task ID");
+        assert_eq!(task.status, TaskStatus::Enqueued, "Task is not enqueued");
+        let assigned_at = env::block_timestamp();
         task.assigned_to = Some(user_account.clone());
-        task.assigned_at = Some(env::block_timestamp());
+        task.assigned_at = Some(assigned_at);
+        task.deadline = Some(assigned_at + self.assignment_ttl_ns);
         self.tasks.insert(&task_id, &task);
-        self.task_queue.remove(self.task_queue.iter().position(|x| *x == task_id).unwrap());
-        self.review_queue.push(&self.review_counter);
-        let review_task = ReviewTask {
-            task_id,
-            reviewed_by: None,
-            reviewed_at: None,
-            is_accepted: false,
-        };
-        self.review_tasks.insert(&self.review_counter, &review_task);
-        self.review_counter += 1;
+        let queue_pos = self.task_queue.iter().position(|x| x == task_id).unwrap() as u64;
+        self.task_queue.swap_remove(queue_pos);
+        self.record_event(task_id, TaskStatus::Assigned, user_account);
     }
 
     // Method to submit a task for review
     pub fn submit_task(&mut self, task_id: u64, descriptions: [Option<String>; 4]) {
-        let task = self.tasks.get(&task_id).expect("Invalid task ID");
-        assert_eq!(task.assigned_to, Some(env::predecessor_account_id()), "You are not assigned to this task");
-        assert_eq!(task.is_completed, false, "Task is already completed");
-        let new_descriptions = descriptions
+        let mut task = self.tasks.get(&task_id).expect("Invalid task ID");
+        let caller = env::predecessor_account_id();
+        assert_eq!(task.assigned_to, Some(caller.clone()), "You are not assigned to this task");
+        assert_eq!(task.status, TaskStatus::Assigned, "Task is not assigned");
+        task.descriptions = descriptions
             .iter()
             .filter_map(|desc| desc.clone())
             .collect::<Vec<String>>()
             .join(";");
-        let updated_task = Task {
-            image_url: task.image_url.clone(),
-            descriptions: new_descriptions,
-            assigned_to: task.assigned_to.clone(),
-            assigned_at: task.assigned_at,
-            reviewed_by: task.reviewed_by.clone(),
-            reviewed_at: task.reviewed_at,
-            is_completed: true,
-        };
-        self.tasks.insert(&task_id, &updated_task);
-        let review_task_id = self.review_tasks.len() as u64;
+        task.is_completed = true;
+        self.tasks.insert(&task_id, &task);
+        self.record_event(task_id, TaskStatus::Submitted, caller);
+
+        let review_task_id = self.review_counter;
         let review_task = ReviewTask {
             task_id,
             reviewed_by: None,
             reviewed_at: None,
             is_accepted: false,
+            retry_count: 0,
+            overturned: false,
+            slashed: false,
         };
         self.review_tasks.insert(&review_task_id, &review_task);
         self.review_queue.push(&review_task_id);
+        self.review_counter += 1;
     }
-      
+
     // Method to assign a review task to a user
     pub fn assign_review_task(&mut self, review_task_id: u64, user_account: String) {
-        assert!(self.review_queue.contains(&review_task_id), "Review task not in queue");
-        let mut review_task = self.review_tasks.get(&review_task_id).unwrap();
-        review_task.reviewed_by = user_account.clone();
-        review_task.reviewed_at = env::block_timestamp();
+        assert!(self.review_queue.iter().any(|x| x == review_task_id), "Review task not in queue");
+        assert!(
+            self.reviewer_stakes.get(&user_account).unwrap_or(0) > 0,
+            "Reviewer must stake a bond before taking review assignments"
+        );
+        let mut review_task = self.review_tasks.get(&review_task_id).expect("Invalid review task ID");
+        assert!(review_task.reviewed_by.is_none(), "Review task already assigned");
+        let task = self.tasks.get(&review_task.task_id).expect("Invalid task ID");
+        assert_eq!(task.status, TaskStatus::Submitted, "Task is not awaiting review");
+        review_task.reviewed_by = Some(user_account.clone());
+        review_task.reviewed_at = Some(env::block_timestamp());
         self.review_tasks.insert(&review_task_id, &review_task);
-        self.review_queue.remove(self.review_queue.iter().position(|x| *x == review_task_id).unwrap());
+        let queue_pos = self.review_queue.iter().position(|x| x == review_task_id).unwrap() as u64;
+        self.review_queue.swap_remove(queue_pos);
+        self.record_event(review_task.task_id, TaskStatus::InReview, user_account);
     }
-      
+
     // Method to accept or reject a task review
     pub fn review_task(&mut self, review_task_id: u64, is_accepted: bool) {
-        assert!(self.review_queue.contains(&review_task_id), "Review task not in queue");
-        let mut review_task = self.review_tasks.get(&review_task_id).unwrap();
-        assert!(review_task.reviewed_by.is_none(), "Review task already assigned");
-        review_task.reviewed_by = Some(user_account.clone());
-        review_task.reviewed_at = Some(env::block_timestamp());
+        let mut review_task = self.review_tasks.get(&review_task_id).expect("Invalid review task ID");
+        assert!(review_task.reviewed_by.is_some(), "Review task is not assigned");
+        let task_id = review_task.task_id;
+        let task = self.tasks.get(&task_id).expect("Invalid task ID");
+        assert_eq!(task.status, TaskStatus::InReview, "Task is not in review");
+
+        review_task.is_accepted = is_accepted;
         self.review_tasks.insert(&review_task_id, &review_task);
-        self.review_queue.remove(self.review_queue.iter().position(|x| *x == review_task_id).unwrap());
-        let task = self.tasks.get(&review_task.task_id).unwrap();
-        if task.descriptions.iter().all(|d| d.is_some()) {
-            review_task.is_accepted = true;
-            self.review_tasks.insert(&review_task_id, &review_task);
+
+        let reviewer = review_task.reviewed_by.clone().unwrap();
+        if is_accepted {
+            self.record_event(task_id, TaskStatus::Accepted, reviewer);
             let payout_amount = U128(1_000_000_000_000_000_000_000_000); // 1 NEAR in yoctoNEAR
             let _promise = Promise::new(self.payout_account.clone())
                 .transfer(payout_amount.0);
         } else {
-            self.review_tasks.insert(&review_task_id, &review_task);
-            self.review_queue.push(&review_task_id);
+            self.record_event(task_id, TaskStatus::Rejected, reviewer);
+            review_task.retry_count += 1;
+            let mut task = self.tasks.get(&task_id).expect("Invalid task ID");
+            task.retry_count += 1;
+            self.tasks.insert(&task_id, &task);
+
+            if review_task.retry_count < self.max_retries {
+                // Send the work back for another pass instead of dropping it.
+                review_task.reviewed_by = None;
+                review_task.reviewed_at = None;
+                self.review_tasks.insert(&review_task_id, &review_task);
+                self.review_queue.push(&review_task_id);
+                self.record_event(task_id, TaskStatus::Submitted, env::predecessor_account_id());
+            } else {
+                // The task has been rejected too many times; park it instead of
+                // looping forever and let an owner decide whether to revive it.
+                self.review_tasks.insert(&review_task_id, &review_task);
+                self.dead_letter_queue.push(&task_id);
+                self.record_event(task_id, TaskStatus::Failed, env::predecessor_account_id());
+            }
         }
     }
-      
+
+    // Method for a reviewer to post a bond before taking review assignments
+    #[payable]
+    pub fn stake_as_reviewer(&mut self) {
+        let account = env::predecessor_account_id();
+        let existing = self.reviewer_stakes.get(&account).unwrap_or(0);
+        self.reviewer_stakes.insert(&account, &(existing + env::attached_deposit()));
+    }
+
+    // Method for a reviewer to withdraw their bond, once they have no in-flight reviews
+    pub fn unstake(&mut self) {
+        let account = env::predecessor_account_id();
+        assert!(!self.has_active_review_assignment(&account), "Cannot unstake with in-flight review assignments");
+        let stake = self.reviewer_stakes.get(&account).unwrap_or(0);
+        assert!(stake > 0, "No stake to withdraw");
+        self.reviewer_stakes.insert(&account, &0);
+        Promise::new(account).transfer(stake);
+    }
+
+    // Owner-only method that marks a previously-accepted review as disputed and
+    // overturned, making its reviewer eligible for `slash_reviewer`.
+    pub fn overturn_review(&mut self, review_task_id: u64) {
+        assert_eq!(env::predecessor_account_id(), self.payout_account, "Only the owner can overturn reviews");
+        let mut review_task = self.review_tasks.get(&review_task_id).expect("Invalid review task ID");
+        assert!(review_task.is_accepted, "Only an accepted review can be overturned");
+        assert!(!review_task.overturned, "Review already overturned");
+        review_task.is_accepted = false;
+        review_task.overturned = true;
+        self.review_tasks.insert(&review_task_id, &review_task);
+    }
+
+    // Owner-only method that confiscates a fraction of a reviewer's bond after a
+    // disputed review is overturned. The slashed amount stays in the contract
+    // balance, acting as the treasury.
+    pub fn slash_reviewer(&mut self, review_task_id: u64) {
+        assert_eq!(env::predecessor_account_id(), self.payout_account, "Only the owner can slash reviewers");
+        let mut review_task = self.review_tasks.get(&review_task_id).expect("Invalid review task ID");
+        assert!(review_task.overturned, "Review must be disputed and overturned before slashing");
+        assert!(!review_task.slashed, "Review has already been slashed");
+        let reviewer = review_task.reviewed_by.clone().expect("Review task has no reviewer to slash");
+
+        let stake = self.reviewer_stakes.get(&reviewer).unwrap_or(0);
+        let penalty = stake * self.slash_fraction_bps as u128 / 10_000;
+        self.reviewer_stakes.insert(&reviewer, &(stake - penalty));
+        let penalty_total = self.reviewer_penalties.get(&reviewer).unwrap_or(0);
+        self.reviewer_penalties.insert(&reviewer, &(penalty_total + penalty));
+
+        review_task.slashed = true;
+        self.review_tasks.insert(&review_task_id, &review_task);
+    }
+
+    // Returns whether a reviewer currently has a review assignment that hasn't
+    // reached a terminal outcome yet.
+    fn has_active_review_assignment(&self, account: &str) -> bool {
+        self.review_tasks.iter().any(|(_, review_task)| {
+            if review_task.reviewed_by.as_deref() != Some(account) {
+                return false;
+            }
+            let task = self.tasks.get(&review_task.task_id).expect("Invalid task ID");
+            task.status == TaskStatus::InReview
+        })
+    }
+
+    // Method to get a reviewer's current staked bond
+    pub fn get_reviewer_stake(&self, account: String) -> Balance {
+        self.reviewer_stakes.get(&account).unwrap_or(0)
+    }
+
+    // Method to get the ids of tasks parked after exhausting their retries
+    pub fn get_dead_letter_queue(&self) -> Vec<u64> {
+        self.dead_letter_queue.to_vec()
+    }
+
+    // Owner-only method to manually revive a dead-lettered task once the
+    // underlying issue (bad data, flaky reviewer, etc.) has been fixed.
+    pub fn requeue_dead_letter(&mut self, task_id: u64) {
+        assert_eq!(env::predecessor_account_id(), self.payout_account, "Only the owner can requeue dead-lettered tasks");
+        let index = self.dead_letter_queue.iter().position(|x| x == task_id).expect("Task is not in the dead letter queue");
+        self.dead_letter_queue.swap_remove(index as u64);
+
+        let mut task = self.tasks.get(&task_id).expect("Invalid task ID");
+        task.assigned_to = None;
+        task.assigned_at = None;
+        task.deadline = None;
+        task.retry_count = 0;
+        self.tasks.insert(&task_id, &task);
+        self.task_queue.push(&task_id);
+        self.record_event(task_id, TaskStatus::Enqueued, env::predecessor_account_id());
+    }
+
     // Method to get the review queue
     pub fn get_review_queue(&self) -> Vec<u64> {
         self.review_queue.to_vec()
@@ -172,4 +423,274 @@ impl TaskManager {
     pub fn get_review_queue_len(&self) -> u64 {
         self.review_queue.len()
     }
+
+    // Method to get the full audit trail of a task, in chronological order
+    pub fn get_task_history(&self, task_id: u64) -> Vec<TaskEvent> {
+        self.task_events.get(&task_id).unwrap_or_default()
+    }
+
+    // Computes the urgency score used to order the task queue. Higher is more urgent.
+    fn urgency_of(&self, task: &Task) -> u64 {
+        let age_in_hours = env::block_timestamp().saturating_sub(task.enqueued_at) / NANOS_PER_HOUR;
+        URGENCY_WEIGHT_PRIORITY * task.priority as u64
+            + URGENCY_WEIGHT_AGE_HOURS * age_in_hours
+            + URGENCY_WEIGHT_RETRY * task.retry_count as u64
+    }
+
+    // Method to get the highest-urgency unassigned task, ties broken by lowest task_id
+    pub fn next_task(&self) -> Option<u64> {
+        self.task_queue
+            .iter()
+            .map(|task_id| {
+                let task = self.tasks.get(&task_id).expect("Invalid task ID");
+                (self.urgency_of(&task), task_id)
+            })
+            .max_by(|(urgency_a, id_a), (urgency_b, id_b)| {
+                urgency_a.cmp(urgency_b).then(id_b.cmp(id_a))
+            })
+            .map(|(_, task_id)| task_id)
+    }
+
+    // Scans assigned-but-not-submitted tasks and re-queues any whose assignment
+    // has expired, so an abandoned assignment doesn't leak out of the pipeline.
+    // Intended to be called periodically by a keeper bot.
+    pub fn reclaim_expired(&mut self) -> Vec<u64> {
+        let now = env::block_timestamp();
+        let caller = env::predecessor_account_id();
+        let expired_ids: Vec<u64> = self
+            .tasks
+            .iter()
+            .filter(|(_, task)| {
+                task.status == TaskStatus::Assigned
+                    && task.deadline.is_some_and(|deadline| deadline < now)
+            })
+            .map(|(task_id, _)| task_id)
+            .collect();
+
+        for task_id in expired_ids.iter().copied() {
+            let mut task = self.tasks.get(&task_id).unwrap();
+            task.assigned_to = None;
+            task.assigned_at = None;
+            task.deadline = None;
+            task.retry_count += 1;
+            self.tasks.insert(&task_id, &task);
+            self.task_queue.push(&task_id);
+            self.record_event(task_id, TaskStatus::Expired, caller.clone());
+            task.status = TaskStatus::Enqueued;
+            self.tasks.insert(&task_id, &task);
+        }
+
+        expired_ids
+    }
+
+    // Method to get a bounded, filtered page of tasks. NEAR view calls have gas
+    // limits, so callers must page through `self.tasks` via `from_index`/`limit`
+    // rather than pulling the whole (unbounded) map at once.
+    pub fn get_tasks(&self, filter: TaskFilter, from_index: u64, limit: u64) -> Vec<(u64, Task)> {
+        self.tasks
+            .iter()
+            .filter(|(_, task)| filter.matches(task))
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    // Method to count tasks matching a filter, for clients paging through `get_tasks`
+    pub fn count_tasks(&self, filter: TaskFilter) -> u64 {
+        self.tasks
+            .iter()
+            .filter(|(_, task)| filter.matches(task))
+            .count() as u64
+    }
+
+    // Method to get the queued task ids ordered by descending urgency
+    pub fn get_task_queue_sorted(&self) -> Vec<u64> {
+        let mut ids_with_urgency: Vec<(u64, u64)> = self
+            .task_queue
+            .iter()
+            .map(|task_id| {
+                let task = self.tasks.get(&task_id).expect("Invalid task ID");
+                (task_id, self.urgency_of(&task))
+            })
+            .collect();
+        ids_with_urgency.sort_by(|(id_a, urgency_a), (id_b, urgency_b)| {
+            urgency_b.cmp(urgency_a).then(id_a.cmp(id_b))
+        });
+        ids_with_urgency.into_iter().map(|(task_id, _)| task_id).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::{testing_env, MockedBlockchain};
+
+    fn context(predecessor: &str, attached_deposit: Balance, block_timestamp: u64) -> near_sdk::VMContext {
+        VMContextBuilder::new()
+            .predecessor_account_id(predecessor.to_string().try_into().unwrap())
+            .attached_deposit(attached_deposit)
+            .block_timestamp(block_timestamp)
+            .is_view(false)
+            .build()
+    }
+
+    fn new_contract() -> TaskManager {
+        TaskManager::new("payout.testnet".to_string(), 1_000, 5_000, 2)
+    }
+
+    #[test]
+    fn full_lifecycle_records_history_and_pays_out() {
+        testing_env!(context("owner.testnet", 0, 0));
+        let mut contract = new_contract();
+
+        let (task_id, is_new) = contract.add_task("img1".to_string(), 5, None);
+        assert!(is_new);
+
+        testing_env!(context("worker.testnet", 0, 1));
+        contract.assign_task(task_id, "worker.testnet".to_string());
+        contract.submit_task(task_id, [Some("a".to_string()), None, None, None]);
+
+        testing_env!(context("reviewer.testnet", 1_000_000, 2));
+        contract.stake_as_reviewer();
+        let review_task_id = contract.get_review_queue()[0];
+        contract.assign_review_task(review_task_id, "reviewer.testnet".to_string());
+        contract.review_task(review_task_id, true);
+
+        let task = contract.get_task(task_id).unwrap();
+        assert_eq!(task.status, TaskStatus::Accepted);
+
+        let history = contract.get_task_history(task_id);
+        let statuses: Vec<TaskStatus> = history.iter().map(|e| e.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                TaskStatus::Enqueued,
+                TaskStatus::Assigned,
+                TaskStatus::Submitted,
+                TaskStatus::InReview,
+                TaskStatus::Accepted,
+            ]
+        );
+    }
+
+    #[test]
+    fn assign_review_task_requires_stake() {
+        testing_env!(context("owner.testnet", 0, 0));
+        let mut contract = new_contract();
+        let (task_id, _) = contract.add_task("img1".to_string(), 5, None);
+        contract.assign_task(task_id, "worker.testnet".to_string());
+
+        testing_env!(context("worker.testnet", 0, 1));
+        contract.submit_task(task_id, [None, None, None, None]);
+
+        let review_task_id = contract.get_review_queue()[0];
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.assign_review_task(review_task_id, "unstaked.testnet".to_string())
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn add_task_dedups_active_tasks_by_image_url() {
+        testing_env!(context("owner.testnet", 0, 0));
+        let mut contract = new_contract();
+        let (first_id, first_is_new) = contract.add_task("same.png".to_string(), 1, None);
+        let (second_id, second_is_new) = contract.add_task("same.png".to_string(), 1, None);
+        assert!(first_is_new);
+        assert!(!second_is_new);
+        assert_eq!(first_id, second_id);
+        assert_eq!(contract.get_task_queue_len(), 1);
+    }
+
+    #[test]
+    fn get_task_queue_sorted_orders_by_priority() {
+        testing_env!(context("owner.testnet", 0, 0));
+        let mut contract = new_contract();
+        let (low_priority, _) = contract.add_task("a.png".to_string(), 1, None);
+        let (high_priority, _) = contract.add_task("b.png".to_string(), 9, None);
+        assert_eq!(contract.get_task_queue_sorted(), vec![high_priority, low_priority]);
+        assert_eq!(contract.next_task(), Some(high_priority));
+    }
+
+    #[test]
+    fn reclaim_expired_requeues_and_bumps_retry_count() {
+        testing_env!(context("owner.testnet", 0, 0));
+        let mut contract = new_contract();
+        let (task_id, _) = contract.add_task("img.png".to_string(), 1, None);
+        contract.assign_task(task_id, "worker.testnet".to_string());
+
+        testing_env!(context("keeper.testnet", 0, 2_000));
+        let reclaimed = contract.reclaim_expired();
+        assert_eq!(reclaimed, vec![task_id]);
+
+        let task = contract.get_task(task_id).unwrap();
+        assert_eq!(task.status, TaskStatus::Enqueued);
+        assert_eq!(task.retry_count, 1);
+        assert!(task.assigned_to.is_none());
+        assert_eq!(contract.get_task_queue_len(), 1);
+    }
+
+    #[test]
+    fn rejections_beyond_max_retries_move_task_to_dead_letter() {
+        testing_env!(context("owner.testnet", 0, 0));
+        let mut contract = new_contract();
+        let (task_id, _) = contract.add_task("img.png".to_string(), 1, None);
+
+        testing_env!(context("worker.testnet", 0, 1));
+        contract.assign_task(task_id, "worker.testnet".to_string());
+        contract.submit_task(task_id, [None, None, None, None]);
+
+        testing_env!(context("reviewer.testnet", 1_000_000, 1));
+        contract.stake_as_reviewer();
+        let review_task_id = contract.get_review_queue()[0];
+
+        // max_retries is 2: a rejection re-queues the same review_task_id for
+        // another pass rather than sending the task back to a worker, so both
+        // rejections are reviews of the original submission.
+        for _ in 0..2 {
+            testing_env!(context("reviewer.testnet", 0, 1));
+            contract.assign_review_task(review_task_id, "reviewer.testnet".to_string());
+            contract.review_task(review_task_id, false);
+        }
+
+        let task = contract.get_task(task_id).unwrap();
+        assert_eq!(task.status, TaskStatus::Failed);
+        assert_eq!(task.retry_count, 2);
+        assert_eq!(contract.get_dead_letter_queue(), vec![task_id]);
+    }
+
+    #[test]
+    fn slash_reviewer_requires_overturn_and_is_idempotent() {
+        testing_env!(context("owner.testnet", 0, 0));
+        let mut contract = new_contract();
+        let (task_id, _) = contract.add_task("img.png".to_string(), 1, None);
+        contract.assign_task(task_id, "worker.testnet".to_string());
+
+        testing_env!(context("worker.testnet", 0, 1));
+        contract.submit_task(task_id, [None, None, None, None]);
+
+        testing_env!(context("reviewer.testnet", 1_000_000, 1));
+        contract.stake_as_reviewer();
+        let review_task_id = contract.get_review_queue()[0];
+        contract.assign_review_task(review_task_id, "reviewer.testnet".to_string());
+        contract.review_task(review_task_id, true);
+
+        testing_env!(context("payout.testnet", 0, 1));
+        let before_overturn = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.slash_reviewer(review_task_id)
+        }));
+        assert!(before_overturn.is_err());
+
+        contract.overturn_review(review_task_id);
+        contract.slash_reviewer(review_task_id);
+        let stake_after_first_slash = contract.get_reviewer_stake("reviewer.testnet".to_string());
+        assert!(stake_after_first_slash < 1_000_000);
+
+        let second_slash = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.slash_reviewer(review_task_id)
+        }));
+        assert!(second_slash.is_err());
+        assert_eq!(contract.get_reviewer_stake("reviewer.testnet".to_string()), stake_after_first_slash);
+    }
 }